@@ -9,9 +9,7 @@ use esp_idf_svc::hal::{
     prelude::*,
 };
 
-pub mod bme280;
-
-use crate::bme280::*;
+use rs_temperature::bme280::*;
 
 fn main() -> Result<()> {
     // It is necessary to call this function once. Otherwise some patches to the runtime
@@ -31,7 +29,7 @@ fn main() -> Result<()> {
         .timeout(Duration::from_micros(200).into());
     let i2c = I2cDriver::new(peripherals.i2c0, sda, scl, &config)?;
 
-    let mut sensor = BME280::new(i2c, DeviceAddr::AD1)?;
+    let mut sensor = BME280::new_i2c(i2c, DeviceAddr::AD1)?;
 
     println!("Sensor init");
 
@@ -1,126 +1,131 @@
-use super::error::{Error, ErrorKind};
+use super::error::Error;
+use super::variant::Variant;
 
 #[derive(Debug, Default)]
 pub struct CalibrationData {
-    dig_T1: u16,
-    dig_T2: i16,
-    dig_T3: i16,
-
-    dig_P1: u16,
-    dig_P2: i16,
-    dig_P3: i16,
-    dig_P4: i16,
-    dig_P5: i16,
-    dig_P6: i16,
-    dig_P7: i16,
-    dig_P8: i16,
-    dig_P9: i16,
-
-    dig_H1: u8,
-    dig_H2: i16,
-    dig_H3: u8,
-    dig_H4: i16,
-    dig_H5: i16,
-    dig_H6: i8,
+    dig_t1: u16,
+    /// dig_T2, dig_T3
+    dig_t: [i16; 2],
+
+    dig_p1: u16,
+    /// dig_P2 ..= dig_P9
+    dig_p: [i16; 8],
+
+    dig_h1: u8,
+    dig_h2: i16,
+    dig_h3: u8,
+    dig_h4: i16,
+    dig_h5: i16,
+    dig_h6: i8,
 
     t_fine: i32,
 }
 
-macro_rules! concat_bytes {
-    ($x:ty, $v:expr, $i:literal) => {
-        concat_bytes!($x, $v[$i + 1], $v[$i])
-    };
-    ($x:ty, $a:expr, $b:expr) => {
-        ($a as $x) << 8 | ($b as $x)
-    };
+/// Unpacks the 12-bit signed `dig_H4`/`dig_H5` pair from the three bytes at registers
+/// 0xE4-0xE6, which share a nibble (datasheet section 4.2.2).
+fn unpack_dig_h4_h5(e4: u8, e5_lsb_e4_lsb: u8, e5_msb: u8) -> (i16, i16) {
+    let h4 = (e4 as i16) << 4 | (e5_lsb_e4_lsb as i16) & 0b1111;
+    let h5 = (e5_lsb_e4_lsb as i16) >> 4 | (e5_msb as i16) << 4;
+    (h4, h5)
 }
 
 impl CalibrationData {
-    pub fn from_vec(input: Vec<u8>) -> Result<CalibrationData, Error> {
-        if input.len() != 42 {
-            return Err(ErrorKind::CalibrationLengthError.into());
+    /// Parses the calibration block read from `Calib00` (and, for `BME280`, `Calib26`).
+    ///
+    /// BMP280 has no humidity channel, so `input` is 26 bytes for `Variant::Bmp280` and 42
+    /// bytes for `Variant::Bme280`.
+    pub fn from_slice<E>(input: &[u8], variant: Variant) -> Result<CalibrationData, Error<E>> {
+        let expected_len = if variant.has_humidity() { 42 } else { 26 };
+        if input.len() != expected_len {
+            return Err(Error::CalibrationLength);
         }
 
-        let cal = CalibrationData {
-            dig_T1: concat_bytes!(u16, input, 0),
-            dig_T2: concat_bytes!(i16, input, 2),
-            dig_T3: concat_bytes!(i16, input, 4),
-
-            dig_P1: concat_bytes!(u16, input, 6),
-            dig_P2: concat_bytes!(i16, input, 8),
-            dig_P3: concat_bytes!(i16, input, 10),
-            dig_P4: concat_bytes!(i16, input, 12),
-            dig_P5: concat_bytes!(i16, input, 14),
-            dig_P6: concat_bytes!(i16, input, 16),
-            dig_P7: concat_bytes!(i16, input, 18),
-            dig_P8: concat_bytes!(i16, input, 20),
-            dig_P9: concat_bytes!(i16, input, 22),
-
-            dig_H1: input[25],
-            dig_H2: concat_bytes!(i16, input, 26),
-            dig_H3: input[28],
-            dig_H4: (input[29] as i16) << 4 | (input[30] as i16) & 0b1111,
-            dig_H5: (input[30] as i16) >> 4 | (input[31] as i16) << 4,
-            dig_H6: input[32] as i8,
-
-            t_fine: 0,
+        let le_u16 = |i: usize| u16::from_le_bytes([input[i], input[i + 1]]);
+        let le_i16 = |i: usize| i16::from_le_bytes([input[i], input[i + 1]]);
+
+        let mut cal = CalibrationData {
+            dig_t1: le_u16(0),
+            dig_t: [le_i16(2), le_i16(4)],
+
+            dig_p1: le_u16(6),
+            dig_p: [
+                le_i16(8),
+                le_i16(10),
+                le_i16(12),
+                le_i16(14),
+                le_i16(16),
+                le_i16(18),
+                le_i16(20),
+                le_i16(22),
+            ],
+
+            ..CalibrationData::default()
         };
 
+        if variant.has_humidity() {
+            cal.dig_h1 = input[25];
+            cal.dig_h2 = le_i16(26);
+            cal.dig_h3 = input[28];
+            (cal.dig_h4, cal.dig_h5) = unpack_dig_h4_h5(input[29], input[30], input[31]);
+            cal.dig_h6 = input[32] as i8;
+        }
+
         Ok(cal)
     }
 
-    pub fn compensate_temperature(&mut self, raw_temperature: i32) -> Result<i32, Error> {
-        let var1: i32 =
-            (((raw_temperature >> 3) - ((self.dig_T1 as i32) << 1)) * (self.dig_T2 as i32)) >> 11;
-        let var2: i32 = (((((raw_temperature >> 4) - (self.dig_T1 as i32))
-            * ((raw_temperature >> 4) - (self.dig_T1 as i32)))
+    pub fn compensate_temperature(&mut self, raw_temperature: i32) -> i32 {
+        let dig_t1 = self.dig_t1 as i32;
+        let [dig_t2, dig_t3] = self.dig_t.map(i32::from);
+
+        let var1: i32 = ((raw_temperature >> 3) - (dig_t1 << 1)) * dig_t2 >> 11;
+        let var2: i32 = (((raw_temperature >> 4) - dig_t1) * ((raw_temperature >> 4) - dig_t1)
             >> 12)
-            * (self.dig_T3 as i32))
+            * dig_t3
             >> 14;
         self.t_fine = var1 + var2;
 
-        dbg!(raw_temperature);
-        dbg!(var1);
-        dbg!(var2);
+        (self.t_fine * 5 + 128) >> 8
+    }
 
-        let temperature = (self.t_fine * 5 + 128) >> 8;
+    pub fn compensate_pressure(&self, raw_pressure: i32) -> u32 {
+        let dig_p1 = self.dig_p1 as i64;
+        let [dig_p2, dig_p3, dig_p4, dig_p5, dig_p6, dig_p7, dig_p8, dig_p9] =
+            self.dig_p.map(i64::from);
 
-        Ok(temperature)
-    }
-    pub fn compensate_pressure(&self, raw_pressure: i32) -> Result<u32, Error> {
         let mut var1: i64 = (self.t_fine as i64) - 128000;
-        let mut var2: i64 = var1 * var1 * (self.dig_P6 as i64);
-        var2 = var2 + ((var1 * (self.dig_P5 as i64)) << 17);
-        var2 = var2 + ((self.dig_P4 as i64) << 35);
-        var1 = ((var1 * var1 * (self.dig_P3 as i64)) >> 8) + ((var1 * (self.dig_P2 as i64)) << 12);
-        var1 = ((1i64 << 47) + var1) * (self.dig_P1 as i64) >> 33;
+        let mut var2: i64 = var1 * var1 * dig_p6;
+        var2 += (var1 * dig_p5) << 17;
+        var2 += dig_p4 << 35;
+        var1 = ((var1 * var1 * dig_p3) >> 8) + ((var1 * dig_p2) << 12);
+        var1 = ((1i64 << 47) + var1) * dig_p1 >> 33;
 
         if var1 == 0 {
-            return Ok(0);
+            return 0;
         }
 
         let mut pressure: i64 = 1048576i64 - (raw_pressure as i64);
         pressure = (((pressure << 31) - var2) * 3125) / var1;
-        var1 = ((self.dig_P9 as i64) * (pressure >> 13) * (pressure >> 13)) >> 25;
-        var2 = ((self.dig_P8 as i64) * pressure) >> 19;
+        var1 = (dig_p9 * (pressure >> 13) * (pressure >> 13)) >> 25;
+        var2 = (dig_p8 * pressure) >> 19;
 
-        pressure = ((pressure + var1 + var2) >> 8) + ((self.dig_P7 as i64) << 4);
+        pressure = ((pressure + var1 + var2) >> 8) + (dig_p7 << 4);
 
-        Ok(pressure as u32)
+        pressure as u32
     }
-    pub fn compensate_humidity(&self, raw_humidity: u16) -> Result<u32, Error> {
+
+    pub fn compensate_humidity(&self, raw_humidity: u16) -> u32 {
         let var1: i32 = self.t_fine - 76800;
         let mut var2: i32 = (raw_humidity as i32) * 16384;
-        let mut var3: i32 = (self.dig_H4 as i32) * 1048576;
-        let mut var4: i32 = (self.dig_H5 as i32) * var1;
+        let mut var3: i32 = (self.dig_h4 as i32) * 1048576;
+        let mut var4: i32 = (self.dig_h5 as i32) * var1;
         let mut var5: i32 = (((var2 - var3) - var4) + 16384) / 32768;
-        var2 = (var1 * (self.dig_H6 as i32)) / 1024;
-        var3 = (var1 * (self.dig_H3 as i32)) / 2048;
+        var2 = (var1 * (self.dig_h6 as i32)) / 1024;
+        var3 = (var1 * (self.dig_h3 as i32)) / 2048;
         var4 = ((var2 * (var3 + 32768)) / 1024) + 2097152;
-        var2 = ((var4 * (self.dig_H2 as i32)) + 8192) / 16384;
+        var2 = ((var4 * (self.dig_h2 as i32)) + 8192) / 16384;
         var3 = var5 * var2;
         var4 = ((var3 / 32768) * (var3 / 32768)) / 128;
-        var5 = var3 - ((var4 * (self.dig_H1 as i32)) / 16);
+        var5 = var3 - ((var4 * (self.dig_h1 as i32)) / 16);
 
         if var5 < 0 {
             var5 = 0;
@@ -138,6 +143,208 @@ impl CalibrationData {
             humidity = humidity_max;
         }
 
-        Ok(humidity as u32)
+        humidity as u32
+    }
+
+    /// Double-precision equivalent of [`compensate_temperature`](Self::compensate_temperature),
+    /// returning degrees Celsius directly (datasheet section 4.2.3).
+    pub fn compensate_temperature_f64(&mut self, raw_temperature: i32) -> f64 {
+        let adc_t = raw_temperature as f64;
+        let dig_t1 = self.dig_t1 as f64;
+        let [dig_t2, dig_t3] = self.dig_t.map(f64::from);
+
+        let var1 = (adc_t / 16384.0 - dig_t1 / 1024.0) * dig_t2;
+        let var2 = (adc_t / 131072.0 - dig_t1 / 8192.0)
+            * (adc_t / 131072.0 - dig_t1 / 8192.0)
+            * dig_t3;
+
+        self.t_fine = (var1 + var2) as i32;
+
+        (var1 + var2) / 5120.0
+    }
+
+    /// Double-precision equivalent of [`compensate_pressure`](Self::compensate_pressure),
+    /// returning pascals directly (datasheet section 4.2.3).
+    pub fn compensate_pressure_f64(&self, raw_pressure: i32) -> f64 {
+        let dig_p1 = self.dig_p1 as f64;
+        let [dig_p2, dig_p3, dig_p4, dig_p5, dig_p6, dig_p7, dig_p8, dig_p9] =
+            self.dig_p.map(f64::from);
+
+        let mut var1 = (self.t_fine as f64) / 2.0 - 64000.0;
+        let mut var2 = var1 * var1 * dig_p6 / 32768.0;
+        var2 += var1 * dig_p5 * 2.0;
+        var2 = var2 / 4.0 + dig_p4 * 65536.0;
+        var1 = (dig_p3 * var1 * var1 / 524288.0 + dig_p2 * var1) / 524288.0;
+        var1 = (1.0 + var1 / 32768.0) * dig_p1;
+
+        if var1 == 0.0 {
+            return 0.0;
+        }
+
+        let mut pressure = 1048576.0 - raw_pressure as f64;
+        pressure = (pressure - var2 / 4096.0) * 6250.0 / var1;
+        var1 = dig_p9 * pressure * pressure / 2147483648.0;
+        var2 = pressure * dig_p8 / 32768.0;
+
+        pressure + (var1 + var2 + dig_p7) / 16.0
     }
-}
\ No newline at end of file
+
+    /// Double-precision equivalent of [`compensate_humidity`](Self::compensate_humidity),
+    /// returning %RH directly (datasheet section 4.2.3).
+    pub fn compensate_humidity_f64(&self, raw_humidity: u16) -> f64 {
+        let dig_h1 = self.dig_h1 as f64;
+        let dig_h2 = self.dig_h2 as f64;
+        let dig_h3 = self.dig_h3 as f64;
+        let dig_h4 = self.dig_h4 as f64;
+        let dig_h5 = self.dig_h5 as f64;
+        let dig_h6 = self.dig_h6 as f64;
+
+        let var_h = self.t_fine as f64 - 76800.0;
+        let mut humidity = (raw_humidity as f64 - (dig_h4 * 64.0 + dig_h5 / 16384.0 * var_h))
+            * (dig_h2
+                / 65536.0
+                * (1.0 + dig_h6 / 67108864.0 * var_h * (1.0 + dig_h3 / 67108864.0 * var_h)));
+        humidity *= 1.0 - dig_h1 * humidity / 524288.0;
+
+        humidity.clamp(0.0, 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_slice_parses_bmp280_26_byte_buffer() {
+        let mut input = [0u8; 26];
+        input[0..2].copy_from_slice(&100u16.to_le_bytes());
+        input[2..4].copy_from_slice(&200i16.to_le_bytes());
+        input[4..6].copy_from_slice(&(-300i16).to_le_bytes());
+        input[6..8].copy_from_slice(&400u16.to_le_bytes());
+        input[8..10].copy_from_slice(&(-1i16).to_le_bytes());
+        input[10..12].copy_from_slice(&2i16.to_le_bytes());
+        input[12..14].copy_from_slice(&3i16.to_le_bytes());
+        input[14..16].copy_from_slice(&4i16.to_le_bytes());
+        input[16..18].copy_from_slice(&5i16.to_le_bytes());
+        input[18..20].copy_from_slice(&6i16.to_le_bytes());
+        input[20..22].copy_from_slice(&7i16.to_le_bytes());
+        input[22..24].copy_from_slice(&8i16.to_le_bytes());
+
+        let cal = CalibrationData::from_slice::<core::convert::Infallible>(&input, Variant::Bmp280)
+            .unwrap();
+
+        assert_eq!(cal.dig_t1, 100);
+        assert_eq!(cal.dig_t, [200, -300]);
+        assert_eq!(cal.dig_p1, 400);
+        assert_eq!(cal.dig_p, [-1, 2, 3, 4, 5, 6, 7, 8]);
+        // BMP280 has no humidity channel, so those fields stay at their `Default` value.
+        assert_eq!(cal.dig_h1, 0);
+        assert_eq!(cal.dig_h2, 0);
+    }
+
+    #[test]
+    fn from_slice_rejects_wrong_length_for_variant() {
+        let input = [0u8; 26];
+
+        assert!(matches!(
+            CalibrationData::from_slice::<core::convert::Infallible>(&input, Variant::Bme280),
+            Err(Error::CalibrationLength)
+        ));
+    }
+
+    #[test]
+    fn unpack_dig_h4_h5_round_trips_known_byte_triples() {
+        // e4=0x0b, e5=0x46 (shared nibble), e6=0x11 => H4 takes e4's byte plus e5's low
+        // nibble, H5 takes e6's byte plus e5's high nibble (datasheet section 4.2.2).
+        assert_eq!(unpack_dig_h4_h5(0x0b, 0x46, 0x11), (182, 276));
+        // All-zero bytes trivially unpack to zero.
+        assert_eq!(unpack_dig_h4_h5(0, 0, 0), (0, 0));
+    }
+
+    /// Bosch datasheet section 8.2 worked example: dig_T1=27504, dig_T2=26435, dig_T3=-1000,
+    /// adc_T=519888 compensates to t_fine=128422, T=25.08°C.
+    #[test]
+    fn compensate_temperature_f64_matches_bosch_reference_vector() {
+        let mut fixed = CalibrationData {
+            dig_t1: 27504,
+            dig_t: [26435, -1000],
+            ..CalibrationData::default()
+        };
+        let mut float = CalibrationData {
+            dig_t1: 27504,
+            dig_t: [26435, -1000],
+            ..CalibrationData::default()
+        };
+
+        let t_fixed = fixed.compensate_temperature(519888);
+        let t_float = float.compensate_temperature_f64(519888);
+
+        assert_eq!(fixed.t_fine, 128422);
+        assert_eq!(float.t_fine, 128422);
+        assert!((t_fixed as f64 / 100.0 - 25.08).abs() < 0.01);
+        assert!((t_float - 25.08).abs() < 0.01);
+    }
+
+    /// Extends the Bosch reference vector with representative pressure coefficients and checks
+    /// the f64 path agrees with the fixed-point path, within fixed-point quantization error.
+    #[test]
+    fn compensate_pressure_f64_agrees_with_fixed_point() {
+        let mut fixed = CalibrationData {
+            dig_t1: 27504,
+            dig_t: [26435, -1000],
+            dig_p1: 36477,
+            dig_p: [-10685, 3024, 2855, 140, -7, 15500, -14600, 6000],
+            ..CalibrationData::default()
+        };
+        let mut float = CalibrationData {
+            dig_t1: 27504,
+            dig_t: [26435, -1000],
+            dig_p1: 36477,
+            dig_p: [-10685, 3024, 2855, 140, -7, 15500, -14600, 6000],
+            ..CalibrationData::default()
+        };
+
+        fixed.compensate_temperature(519888);
+        float.compensate_temperature_f64(519888);
+
+        let p_fixed = fixed.compensate_pressure(415148) as f64 / 256.0;
+        let p_float = float.compensate_pressure_f64(415148);
+
+        assert!((p_fixed - p_float).abs() < 0.1);
+    }
+
+    /// Same cross-check as the pressure test, for the humidity compensation path.
+    #[test]
+    fn compensate_humidity_f64_agrees_with_fixed_point() {
+        let mut fixed = CalibrationData {
+            dig_t1: 27504,
+            dig_t: [26435, -1000],
+            dig_h1: 75,
+            dig_h2: 362,
+            dig_h3: 0,
+            dig_h4: 341,
+            dig_h5: 0,
+            dig_h6: 30,
+            ..CalibrationData::default()
+        };
+        let mut float = CalibrationData {
+            dig_t1: 27504,
+            dig_t: [26435, -1000],
+            dig_h1: 75,
+            dig_h2: 362,
+            dig_h3: 0,
+            dig_h4: 341,
+            dig_h5: 0,
+            dig_h6: 30,
+            ..CalibrationData::default()
+        };
+
+        fixed.compensate_temperature(519888);
+        float.compensate_temperature_f64(519888);
+
+        let h_fixed = fixed.compensate_humidity(23616) as f64 / 1024.0;
+        let h_float = float.compensate_humidity_f64(23616);
+
+        assert!((h_fixed - h_float).abs() < 0.1);
+    }
+}
@@ -0,0 +1,145 @@
+/// Oversampling factor applied to a single measurement channel.
+///
+/// The numeric value matches the `osrs_*` field encoding used by `ctrl_hum` and `ctrl_meas`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Oversampling {
+    Skipped = 0,
+    X1 = 1,
+    X2 = 2,
+    X4 = 3,
+    X8 = 4,
+    X16 = 5,
+}
+
+impl Oversampling {
+    /// Effective number of samples averaged by this setting, 0 if the channel is skipped.
+    pub(crate) fn samples(&self) -> u32 {
+        match self {
+            Oversampling::Skipped => 0,
+            Oversampling::X1 => 1,
+            Oversampling::X2 => 2,
+            Oversampling::X4 => 4,
+            Oversampling::X8 => 8,
+            Oversampling::X16 => 16,
+        }
+    }
+}
+
+/// Sensor power mode, see datasheet section 3.3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PowerMode {
+    Sleep = 0b00,
+    Forced = 0b01,
+    Normal = 0b11,
+}
+
+/// IIR filter coefficient applied to pressure and temperature, see datasheet section 3.4.4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Filter {
+    Off = 0,
+    X2 = 1,
+    X4 = 2,
+    X8 = 3,
+    X16 = 4,
+}
+
+/// Inactive duration between measurements in normal mode, see datasheet table 7.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum StandbyTime {
+    Ms0_5 = 0b000,
+    Ms62_5 = 0b001,
+    Ms125 = 0b010,
+    Ms250 = 0b011,
+    Ms500 = 0b100,
+    Ms1000 = 0b101,
+    Ms10 = 0b110,
+    Ms20 = 0b111,
+}
+
+/// Sensor configuration covering `ctrl_hum` (0xF2), `ctrl_meas` (0xF4) and `config` (0xF5).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Config {
+    pub osrs_t: Oversampling,
+    pub osrs_p: Oversampling,
+    pub osrs_h: Oversampling,
+    pub mode: PowerMode,
+    pub filter: Filter,
+    pub standby_time: StandbyTime,
+    /// Enables 3-wire SPI mode (`spi3w_en`). Leave `false` for I²C or 4-wire SPI.
+    pub spi3w_en: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            osrs_t: Oversampling::X1,
+            osrs_p: Oversampling::X1,
+            osrs_h: Oversampling::X1,
+            mode: PowerMode::Forced,
+            filter: Filter::Off,
+            standby_time: StandbyTime::Ms0_5,
+            spi3w_en: false,
+        }
+    }
+}
+
+impl Config {
+    /// Value to write to `ctrl_hum` (0xF2). Only takes effect after a following write to
+    /// `ctrl_meas`, see datasheet section 5.4.3.
+    pub(crate) fn ctrl_hum(&self) -> u8 {
+        self.osrs_h as u8
+    }
+
+    /// Value to write to `ctrl_meas` (0xF4).
+    pub(crate) fn ctrl_meas(&self) -> u8 {
+        (self.osrs_t as u8) << 5 | (self.osrs_p as u8) << 2 | self.mode as u8
+    }
+
+    /// Value to write to `config` (0xF5).
+    pub(crate) fn config(&self) -> u8 {
+        (self.standby_time as u8) << 5 | (self.filter as u8) << 2 | (self.spi3w_en as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_matches_datasheet_encoding() {
+        let cfg = Config::default();
+
+        assert_eq!(cfg.ctrl_hum(), 0b001);
+        assert_eq!(cfg.ctrl_meas(), 0b00100101);
+        assert_eq!(cfg.config(), 0b000_000_0);
+    }
+
+    #[test]
+    fn ctrl_meas_packs_oversampling_and_mode() {
+        let cfg = Config {
+            osrs_t: Oversampling::X16,
+            osrs_p: Oversampling::X4,
+            mode: PowerMode::Normal,
+            ..Config::default()
+        };
+
+        assert_eq!(cfg.ctrl_meas(), 0b101_011_11);
+    }
+
+    #[test]
+    fn config_register_packs_standby_filter_and_spi3w_en() {
+        let cfg = Config {
+            standby_time: StandbyTime::Ms250,
+            filter: Filter::X16,
+            spi3w_en: true,
+            ..Config::default()
+        };
+
+        assert_eq!(cfg.config(), 0b0111_0001);
+    }
+}
@@ -0,0 +1,139 @@
+use embedded_hal::i2c::I2c;
+use embedded_hal::spi::{Operation, SpiDevice};
+
+use super::register::Register;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeviceAddr {
+    /// 0x76
+    AD0 = 0b111_0110,
+    /// 0x77
+    AD1 = 0b111_0111,
+}
+
+/// Abstracts over the physical bus used to reach the sensor's registers, so [`BME280`](
+/// super::BME280) can be driven over I²C or SPI alike.
+///
+/// This is internal plumbing, not part of the public API: callers construct a driver via
+/// [`BME280::new_i2c`](super::BME280::new_i2c) or [`BME280::new_spi`](super::BME280::new_spi)
+/// rather than implementing this trait themselves.
+pub(crate) trait Interface {
+    type Error;
+
+    fn read_register(&mut self, register: Register, buffer: &mut [u8]) -> Result<(), Self::Error>;
+    fn write_register(&mut self, register: Register, value: u8) -> Result<(), Self::Error>;
+}
+
+/// I²C backend for [`Interface`].
+pub struct I2cInterface<I2C> {
+    i2c: I2C,
+    address: DeviceAddr,
+}
+
+impl<I2C> I2cInterface<I2C> {
+    pub fn new(i2c: I2C, address: DeviceAddr) -> Self {
+        Self { i2c, address }
+    }
+}
+
+impl<I2C, E> Interface for I2cInterface<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    type Error = E;
+
+    fn read_register(&mut self, register: Register, buffer: &mut [u8]) -> Result<(), E> {
+        self.i2c
+            .write_read(self.address as u8, &[register.address()], buffer)
+    }
+
+    fn write_register(&mut self, register: Register, value: u8) -> Result<(), E> {
+        self.i2c
+            .write(self.address as u8, &[register.address(), value])
+    }
+}
+
+/// 4-wire SPI backend for [`Interface`].
+///
+/// Per the datasheet, register reads set bit 7 of the address (read = MSB 1) while writes clear
+/// it (write = MSB 0). `spi3w_en` in [`Config`](super::Config) should stay `false` for this
+/// 4-wire mode.
+pub struct SpiInterface<SPI> {
+    spi: SPI,
+}
+
+impl<SPI> SpiInterface<SPI> {
+    pub fn new(spi: SPI) -> Self {
+        Self { spi }
+    }
+}
+
+impl<SPI, E> Interface for SpiInterface<SPI>
+where
+    SPI: SpiDevice<Error = E>,
+{
+    type Error = E;
+
+    fn read_register(&mut self, register: Register, buffer: &mut [u8]) -> Result<(), E> {
+        let address = register.address() | 0x80;
+        self.spi
+            .transaction(&mut [Operation::Write(&[address]), Operation::Read(buffer)])
+    }
+
+    fn write_register(&mut self, register: Register, value: u8) -> Result<(), E> {
+        let address = register.address() & 0x7F;
+        self.spi.write(&[address, value])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct MockSpi {
+        last_write: [u8; 2],
+        last_write_len: usize,
+    }
+
+    impl embedded_hal::spi::ErrorType for MockSpi {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SpiDevice for MockSpi {
+        fn transaction(
+            &mut self,
+            operations: &mut [Operation<'_, u8>],
+        ) -> Result<(), Self::Error> {
+            for op in operations {
+                if let Operation::Write(bytes) = op {
+                    self.last_write_len = bytes.len();
+                    self.last_write[..bytes.len()].copy_from_slice(bytes);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn read_register_sets_bit_7() {
+        let mut spi = MockSpi::default();
+        let mut interface = SpiInterface::new(&mut spi);
+        let mut buffer = [0u8; 1];
+        interface
+            .read_register(Register::Id, &mut buffer)
+            .unwrap();
+        assert_eq!(&spi.last_write[..spi.last_write_len], [Register::Id.address() | 0x80]);
+    }
+
+    #[test]
+    fn write_register_clears_bit_7() {
+        let mut spi = MockSpi::default();
+        let mut interface = SpiInterface::new(&mut spi);
+        interface.write_register(Register::Config, 0x2C).unwrap();
+        assert_eq!(
+            &spi.last_write[..spi.last_write_len],
+            [Register::Config.address() & 0x7F, 0x2C]
+        );
+    }
+}
@@ -1,27 +1,34 @@
-use std::fmt;
+use core::fmt;
 
 #[derive(Debug, Default)]
 pub struct RawMeasurement {
     pub Pressure: i32,
     pub Temperature: i32,
-    pub Humidity: u16,
+    pub Humidity: Option<u16>,
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Measurement {
     pub Pressure: f64,
     pub Temperature: f64,
-    pub Humidity: f64,
+    /// `None` on a BMP280, which has no humidity channel.
+    pub Humidity: Option<f64>,
 }
 
 impl fmt::Display for Measurement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "temperature: {:.2}Â°C, pressure: {:.2} hPa, humdity: {:.2}",
+            "temperature: {:.2}°C, pressure: {:.2} hPa",
             self.Temperature,
             self.Pressure / 100.0,
-            self.Humidity
-        )
+        )?;
+
+        if let Some(humidity) = self.Humidity {
+            write!(f, ", humdity: {:.2}", humidity)?;
+        }
+
+        Ok(())
     }
-}
\ No newline at end of file
+}
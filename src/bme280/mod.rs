@@ -0,0 +1,339 @@
+#![deny(unsafe_code)]
+use embedded_hal::delay::DelayNs;
+
+mod calibration;
+mod config;
+mod error;
+mod interface;
+mod measurement;
+mod register;
+mod variant;
+
+pub use calibration::CalibrationData;
+pub use config::{Config, Filter, Oversampling, PowerMode, StandbyTime};
+pub use error::Error;
+pub use interface::{DeviceAddr, I2cInterface, SpiInterface};
+use interface::Interface;
+pub use measurement::{Measurement, RawMeasurement};
+pub use register::Register;
+pub use variant::Variant;
+
+/// Standard atmospheric pressure at sea level, in pascals.
+const DEFAULT_SEA_LEVEL_PRESSURE_PA: f64 = 101325.0;
+
+pub struct BME280<IFACE> {
+    interface: IFACE,
+
+    calibration: CalibrationData,
+
+    config: Config,
+
+    sea_level_pressure: f64,
+
+    variant: Variant,
+}
+
+impl<I2C, E> BME280<I2cInterface<I2C>>
+where
+    I2C: embedded_hal::i2c::I2c<Error = E>,
+    E: core::fmt::Debug,
+{
+    /// Creates a driver instance talking to the sensor over I²C.
+    pub fn new_i2c(i2c: I2C, address: DeviceAddr) -> Result<Self, Error<E>> {
+        Self::new(I2cInterface::new(i2c, address))
+    }
+}
+
+impl<SPI, E> BME280<SpiInterface<SPI>>
+where
+    SPI: embedded_hal::spi::SpiDevice<Error = E>,
+    E: core::fmt::Debug,
+{
+    /// Creates a driver instance talking to the sensor over 4-wire SPI.
+    pub fn new_spi(spi: SPI) -> Result<Self, Error<E>> {
+        Self::new(SpiInterface::new(spi))
+    }
+}
+
+// `Interface` is intentionally pub(crate): it's internal plumbing, not part of the public API.
+#[allow(private_bounds)]
+impl<IFACE, E> BME280<IFACE>
+where
+    IFACE: Interface<Error = E>,
+    E: core::fmt::Debug,
+{
+    pub fn new(interface: IFACE) -> Result<Self, Error<E>> {
+        let mut n = Self {
+            interface,
+            calibration: CalibrationData::default(),
+            config: Config::default(),
+            sea_level_pressure: DEFAULT_SEA_LEVEL_PRESSURE_PA,
+            variant: Variant::Bme280,
+        };
+
+        let chip_id = n.read_device_id_register()?;
+        n.variant = Variant::from_chip_id(chip_id)?;
+
+        n.calibration = n.read_calibration()?;
+        n.set_config(Config::default())?;
+
+        Ok(n)
+    }
+
+    /// The sensor variant detected from the chip ID register (0xD0) in [`new`](Self::new).
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    pub fn read_device_id_register(&mut self) -> Result<u8, E> {
+        let mut buffer = [0u8; 1];
+
+        self.interface.read_register(Register::Id, &mut buffer)?;
+
+        Ok(buffer[0])
+    }
+
+    pub fn read_calibration(&mut self) -> Result<CalibrationData, Error<E>> {
+        let mut buffer = [0u8; 42];
+
+        self.interface
+            .read_register(Register::Calib00, &mut buffer[..26])?;
+
+        let len = if self.variant.has_humidity() {
+            self.interface
+                .read_register(Register::Calib26, &mut buffer[26..])?;
+            42
+        } else {
+            26
+        };
+
+        CalibrationData::from_slice(&buffer[..len], self.variant)
+    }
+
+    /// Writes `ctrl_hum`, `ctrl_meas` and `config` so that the sensor adopts `cfg`.
+    ///
+    /// `ctrl_hum` only takes effect once `ctrl_meas` is written afterwards, so the order here
+    /// matters (datasheet section 5.4.3). BMP280 has no humidity channel and no `ctrl_hum`
+    /// register, so that write is skipped for it.
+    pub fn set_config(&mut self, cfg: Config) -> Result<(), Error<E>> {
+        if self.variant.has_humidity() {
+            self.interface
+                .write_register(Register::CtrlHum, cfg.ctrl_hum())?;
+        }
+        self.interface
+            .write_register(Register::CtrlMeas, cfg.ctrl_meas())?;
+        self.interface
+            .write_register(Register::Config, cfg.config())?;
+
+        self.config = cfg;
+
+        Ok(())
+    }
+
+    fn read_raw_values(&mut self) -> Result<RawMeasurement, Error<E>> {
+        let mut buffer = [0u8; 8];
+
+        let len = if self.variant.has_humidity() { 8 } else { 6 };
+        self.interface
+            .read_register(Register::Pressure, &mut buffer[..len])?;
+
+        Ok(RawMeasurement {
+            Pressure: (buffer[0] as i32) << 12 | (buffer[1] as i32) << 4 | (buffer[2] as i32) >> 4,
+            Temperature: (buffer[3] as i32) << 12
+                | (buffer[4] as i32) << 4
+                | (buffer[5] as i32) >> 4,
+            Humidity: self
+                .variant
+                .has_humidity()
+                .then(|| (buffer[6] as u16) << 8 | (buffer[7] as u16)),
+        })
+    }
+
+    /// Triggers a measurement using the currently configured oversampling and power mode, then
+    /// reads the result.
+    ///
+    /// In [`PowerMode::Forced`], rewriting `ctrl_meas` here re-triggers a conversion (the sensor
+    /// drops back to sleep after each forced measurement); in [`PowerMode::Normal`] it is a
+    /// harmless no-op, since the sensor is already sampling continuously.
+    pub fn measure(&mut self) -> Result<Measurement, Error<E>> {
+        if self.variant.has_humidity() {
+            self.interface
+                .write_register(Register::CtrlHum, self.config.ctrl_hum())?;
+        }
+        self.interface
+            .write_register(Register::CtrlMeas, self.config.ctrl_meas())?;
+
+        let raw = self.read_raw_values()?;
+        Ok(self.compensate(raw))
+    }
+
+    /// Like [`measure`](Self::measure), but compensates using the double-precision datasheet
+    /// formulas instead of the faster fixed-point ones.
+    pub fn measure_float(&mut self) -> Result<Measurement, Error<E>> {
+        if self.variant.has_humidity() {
+            self.interface
+                .write_register(Register::CtrlHum, self.config.ctrl_hum())?;
+        }
+        self.interface
+            .write_register(Register::CtrlMeas, self.config.ctrl_meas())?;
+
+        let raw = self.read_raw_values()?;
+        Ok(self.compensate_f64(raw))
+    }
+
+    /// Triggers a forced-mode measurement using the currently configured oversampling, blocks
+    /// for the worst-case conversion time (datasheet section 9.1), then reads the result.
+    ///
+    /// This avoids racing the ADC conversion, which plain [`measure`](Self::measure) can do
+    /// since it reads the data registers immediately after triggering the measurement.
+    pub fn measure_with_delay<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<Measurement, Error<E>> {
+        let mut cfg = self.config;
+        cfg.mode = PowerMode::Forced;
+        self.set_config(cfg)?;
+
+        delay.delay_ms(libm::ceil(self.max_measurement_time_ms()) as u32);
+
+        let raw = self.read_raw_values()?;
+        Ok(self.compensate(raw))
+    }
+
+    /// Worst-case conversion time in milliseconds for the current oversampling configuration.
+    fn max_measurement_time_ms(&self) -> f64 {
+        let term = |osrs: Oversampling| {
+            let samples = osrs.samples();
+            if samples == 0 {
+                0.0
+            } else {
+                2.3 * samples as f64 + 0.575
+            }
+        };
+
+        1.25 + 2.3 * self.config.osrs_t.samples() as f64
+            + term(self.config.osrs_p)
+            + term(self.config.osrs_h)
+    }
+
+    /// Sets the reference sea-level pressure, in pascals, used by [`altitude`](Self::altitude).
+    /// Defaults to the standard atmosphere, 101325 Pa.
+    pub fn set_sea_level_pressure(&mut self, p0_pa: f64) {
+        self.sea_level_pressure = p0_pa;
+    }
+
+    /// Converts a compensated pressure reading to an altitude in meters above the configured
+    /// sea-level pressure, using the international barometric formula.
+    pub fn altitude(&self, measurement: &Measurement) -> f64 {
+        44330.0 * (1.0 - libm::pow(measurement.Pressure / self.sea_level_pressure, 1.0 / 5.255))
+    }
+
+    /// Calibrates the sea-level pressure from a compensated pressure reading `p` (in pascals)
+    /// taken at a known altitude, in meters. This is the inverse of [`altitude`](Self::altitude).
+    pub fn sea_level_pressure_from(&self, p: f64, known_altitude_m: f64) -> f64 {
+        p / libm::pow(1.0 - known_altitude_m / 44330.0, 5.255)
+    }
+
+    fn compensate(&mut self, raw: RawMeasurement) -> Measurement {
+        Measurement {
+            Temperature: (self.calibration.compensate_temperature(raw.Temperature) as f64) / 100.0,
+            Pressure: (self.calibration.compensate_pressure(raw.Pressure) as f64) / 256.0,
+            Humidity: raw
+                .Humidity
+                .map(|h| (self.calibration.compensate_humidity(h) as f64) / 1024.0),
+        }
+    }
+
+    fn compensate_f64(&mut self, raw: RawMeasurement) -> Measurement {
+        Measurement {
+            Temperature: self.calibration.compensate_temperature_f64(raw.Temperature),
+            Pressure: self.calibration.compensate_pressure_f64(raw.Pressure),
+            Humidity: raw
+                .Humidity
+                .map(|h| self.calibration.compensate_humidity_f64(h)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct DummyInterface;
+
+    impl Interface for DummyInterface {
+        type Error = core::convert::Infallible;
+
+        fn read_register(&mut self, _register: Register, _buffer: &mut [u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn write_register(&mut self, _register: Register, _value: u8) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    fn test_sensor() -> BME280<DummyInterface> {
+        BME280 {
+            interface: DummyInterface,
+            calibration: CalibrationData::default(),
+            config: Config::default(),
+            sea_level_pressure: DEFAULT_SEA_LEVEL_PRESSURE_PA,
+            variant: Variant::Bme280,
+        }
+    }
+
+    #[test]
+    fn max_measurement_time_all_x1() {
+        let mut sensor = test_sensor();
+        sensor.config = Config {
+            osrs_t: Oversampling::X1,
+            osrs_p: Oversampling::X1,
+            osrs_h: Oversampling::X1,
+            ..Config::default()
+        };
+
+        assert!((sensor.max_measurement_time_ms() - 9.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn max_measurement_time_no_humidity() {
+        let mut sensor = test_sensor();
+        sensor.config = Config {
+            osrs_t: Oversampling::X1,
+            osrs_p: Oversampling::X1,
+            osrs_h: Oversampling::Skipped,
+            ..Config::default()
+        };
+
+        assert!((sensor.max_measurement_time_ms() - 6.425).abs() < 1e-9);
+    }
+
+    #[test]
+    fn altitude_is_zero_at_the_configured_sea_level_pressure() {
+        let sensor = test_sensor();
+        let measurement = Measurement {
+            Pressure: DEFAULT_SEA_LEVEL_PRESSURE_PA,
+            ..Measurement::default()
+        };
+
+        assert!(sensor.altitude(&measurement).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sea_level_pressure_from_inverts_altitude() {
+        let mut sensor = test_sensor();
+        let p0 = DEFAULT_SEA_LEVEL_PRESSURE_PA;
+        let known_altitude_m = 1000.0;
+
+        // Pressure a barometer would read at `known_altitude_m` above `p0`, computed
+        // independently of `altitude`/`sea_level_pressure_from` via the barometric formula.
+        let p = p0 * libm::pow(1.0 - known_altitude_m / 44330.0, 5.255);
+
+        sensor.sea_level_pressure = p0;
+        let measurement = Measurement { Pressure: p, ..Measurement::default() };
+
+        assert!((sensor.altitude(&measurement) - known_altitude_m).abs() < 1e-6);
+        assert!((sensor.sea_level_pressure_from(p, known_altitude_m) - p0).abs() < 1e-6);
+    }
+}
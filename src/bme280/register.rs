@@ -4,6 +4,10 @@ pub enum Register {
     Calib00 = 0x88,
     Calib26 = 0xE1,
 
+    CtrlHum = 0xF2,
+    CtrlMeas = 0xF4,
+    Config = 0xF5,
+
     Pressure = 0xF7,
     Temperature = 0xFA,
     Humidity = 0xFD,
@@ -13,4 +17,4 @@ impl Register {
     pub fn address(&self) -> u8 {
         *self as u8
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,56 @@
+use super::error::Error;
+
+/// Sensor variant, distinguished by the chip ID register (0xD0).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Variant {
+    /// Chip ID 0x60: BME280, adds humidity sensing on top of BMP280.
+    Bme280,
+    /// Chip ID 0x58: BMP280, pressure and temperature only.
+    Bmp280,
+}
+
+impl Variant {
+    pub(crate) fn from_chip_id<E>(id: u8) -> Result<Variant, Error<E>> {
+        match id {
+            0x60 => Ok(Variant::Bme280),
+            0x58 => Ok(Variant::Bmp280),
+            _ => Err(Error::UnknownChipId(id)),
+        }
+    }
+
+    pub(crate) fn has_humidity(&self) -> bool {
+        matches!(self, Variant::Bme280)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_chip_id_recognizes_bme280_and_bmp280() {
+        assert!(matches!(
+            Variant::from_chip_id::<core::convert::Infallible>(0x60),
+            Ok(Variant::Bme280)
+        ));
+        assert!(matches!(
+            Variant::from_chip_id::<core::convert::Infallible>(0x58),
+            Ok(Variant::Bmp280)
+        ));
+    }
+
+    #[test]
+    fn from_chip_id_rejects_unknown_id() {
+        assert!(matches!(
+            Variant::from_chip_id::<core::convert::Infallible>(0x00),
+            Err(Error::UnknownChipId(0x00))
+        ));
+    }
+
+    #[test]
+    fn has_humidity_only_for_bme280() {
+        assert!(Variant::Bme280.has_humidity());
+        assert!(!Variant::Bmp280.has_humidity());
+    }
+}